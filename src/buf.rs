@@ -1,3 +1,7 @@
+use std::convert::TryFrom;
+
+use crate::size_of::SizeOf;
+
 /// The Buf trait represents a packet buffer.
 /// A trait is used so that an implementation can be provided that enables building and testing packet
 /// pipelines without needing the AF_XDP infrastructure.
@@ -37,6 +41,137 @@ where
 
     /// Returns a mutable reference to the embeded user struct
     fn get_user_mut(&mut self) -> &mut T;
+
+    /// Returns the offset of the first occurrence of `needle` in the packet data, or `None` if it is not
+    /// present. Scans 64 bytes at a time, as Apache Arrow's buffer does with `u8x64`, so the compiler can
+    /// autovectorize the common case, falling back to a scalar scan for the remaining tail bytes.
+    fn find_byte(&self, needle: u8) -> Option<usize> {
+        let data = &self.get_data()[..self.get_len() as usize];
+
+        let chunks = data.chunks_exact(64);
+        let remainder = chunks.remainder();
+
+        let mut offset = 0;
+        for chunk in chunks {
+            if let Some(i) = chunk.iter().position(|&b| b == needle) {
+                return Some(offset + i);
+            }
+            offset += chunk.len();
+        }
+
+        remainder.iter().position(|&b| b == needle).map(|i| offset + i)
+    }
+
+    /// Returns true if the packet data is byte-for-byte equal to `other`. Compares 64 bytes at a time,
+    /// as Apache Arrow's buffer does with `u8x64`, falling back to a scalar comparison for the remaining
+    /// tail bytes.
+    fn data_eq(&self, other: &[u8]) -> bool {
+        let data = &self.get_data()[..self.get_len() as usize];
+
+        if data.len() != other.len() {
+            return false;
+        }
+
+        let mut chunks = data.chunks_exact(64);
+        let mut other_chunks = other.chunks_exact(64);
+
+        for (a, b) in chunks.by_ref().zip(other_chunks.by_ref()) {
+            if a != b {
+                return false;
+            }
+        }
+
+        chunks.remainder() == other_chunks.remainder()
+    }
+
+    /// Consumes `len` bytes of headroom, growing the packet data at the front of the buffer, and returns
+    /// a mutable slice over the newly prepended bytes so a header can be written into place. This mirrors
+    /// DPDK's `rte_pktmbuf_prepend`. Panics if `len` is greater than the available headroom.
+    fn prepend(&mut self, len: usize) -> &mut [u8] {
+        let headroom = self.get_headroom();
+        if len > headroom {
+            panic!("prepend len too large {} vs headroom {}", len, headroom);
+        }
+
+        let new_headroom = headroom - len;
+        self.set_headroom(new_headroom);
+
+        let new_len = self.get_len() + u16::try_from(len).unwrap();
+        self.set_len(new_len);
+
+        &mut self.get_data_with_headroom_mut()[new_headroom..new_headroom + len]
+    }
+
+    /// Advances past a header of `len` bytes by increasing the headroom, shrinking the packet data at the
+    /// front of the buffer. This mirrors DPDK's `rte_pktmbuf_adj`. Panics if `len` is greater than the
+    /// current packet data length.
+    fn adjust_head(&mut self, len: usize) {
+        let data_len = self.get_len() as usize;
+        if len > data_len {
+            panic!("adjust_head len too large {} vs len {}", len, data_len);
+        }
+
+        self.set_headroom(self.get_headroom() + len);
+        self.set_len(u16::try_from(data_len - len).unwrap());
+    }
+
+    /// Grows the packet data by `len` bytes at the tail of the buffer and returns a mutable slice over
+    /// the newly appended bytes. This mirrors DPDK's `rte_pktmbuf_append`. Panics if `len` does not fit in
+    /// the remaining capacity.
+    fn append(&mut self, len: usize) -> &mut [u8] {
+        let capacity = self.get_capacity() as usize;
+        let cur_len = self.get_len() as usize;
+        if cur_len + len > capacity {
+            panic!(
+                "append len too large {} vs remaining capacity {}",
+                len,
+                capacity - cur_len
+            );
+        }
+
+        self.set_len(u16::try_from(cur_len + len).unwrap());
+
+        &mut self.get_data_mut()[cur_len..cur_len + len]
+    }
+
+    /// Shrinks the packet data by `len` bytes at the tail of the buffer. This mirrors DPDK's
+    /// `rte_pktmbuf_trim`. Panics if `len` is greater than the current packet data length.
+    fn trim(&mut self, len: usize) {
+        let cur_len = self.get_len() as usize;
+        if len > cur_len {
+            panic!("trim len too large {} vs len {}", len, cur_len);
+        }
+
+        self.set_len(u16::try_from(cur_len - len).unwrap());
+    }
+
+    /// Reads a `H` out of the packet data at `offset`, or `None` if `offset + H::size_of()` does not fit
+    /// within the valid packet data length. Lets callers map protocol headers (e.g. Ethernet, IPv4)
+    /// directly onto buffer memory rather than indexing slices by hand.
+    fn read_header<H: SizeOf + Copy>(&self, offset: usize) -> Option<H> {
+        if offset + H::size_of() > self.get_len() as usize {
+            return None;
+        }
+
+        let data = self.get_data();
+        unsafe { Some(std::ptr::read_unaligned(data[offset..].as_ptr() as *const H)) }
+    }
+
+    /// Writes `h` into the packet data at `offset`. Panics if `offset + H::size_of()` does not fit within
+    /// the valid packet data length.
+    fn write_header<H: SizeOf + Copy>(&mut self, offset: usize, h: &H) {
+        if offset + H::size_of() > self.get_len() as usize {
+            panic!(
+                "write_header out of bounds: offset {} + size {} > len {}",
+                offset,
+                H::size_of(),
+                self.get_len()
+            );
+        }
+
+        let data = self.get_data_mut();
+        unsafe { std::ptr::write_unaligned(data[offset..].as_mut_ptr() as *mut H, *h) }
+    }
 }
 
 /*
@@ -50,3 +185,196 @@ pub trait BufConst<T, const N: usize> where T: std::default::Default {
     fn get_user_mut(&mut self) -> &mut T;
 }
 */
+
+#[cfg(test)]
+mod tests {
+    use super::Buf;
+    use std::convert::TryFrom;
+
+    /// A minimal [Buf](super::Buf) impl backed by a plain `Vec<u8>`, used only to exercise the trait's
+    /// default methods against a buffer with a fixed total size and a movable headroom/len.
+    struct TestBuf {
+        data: Vec<u8>,
+        len: u16,
+        headroom: usize,
+        user: (),
+    }
+
+    impl TestBuf {
+        fn new(total_len: usize, headroom: usize) -> TestBuf {
+            TestBuf {
+                data: vec![0u8; total_len],
+                len: 0,
+                headroom,
+                user: (),
+            }
+        }
+    }
+
+    impl Buf<()> for TestBuf {
+        fn get_data(&self) -> &[u8] {
+            &self.data[self.headroom..]
+        }
+
+        fn get_data_mut(&mut self) -> &mut [u8] {
+            &mut self.data[self.headroom..]
+        }
+
+        fn get_data_with_headroom(&self) -> &[u8] {
+            &self.data[..]
+        }
+
+        fn get_data_with_headroom_mut(&mut self) -> &mut [u8] {
+            &mut self.data[..]
+        }
+
+        fn get_capacity(&self) -> u16 {
+            u16::try_from(self.data.len() - self.headroom).unwrap()
+        }
+
+        fn get_len(&self) -> u16 {
+            self.len
+        }
+
+        fn set_headroom(&mut self, headroom: usize) {
+            if headroom > self.data.len() {
+                panic!("headroom too large {} vs {}", headroom, self.data.len());
+            }
+            self.headroom = headroom;
+        }
+
+        fn get_headroom(&self) -> usize {
+            self.headroom
+        }
+
+        fn set_len(&mut self, len: u16) {
+            if len > self.get_capacity() {
+                panic!("len too large {} vs {}", len, self.get_capacity());
+            }
+            self.len = len;
+        }
+
+        fn get_user(&self) -> &() {
+            &self.user
+        }
+
+        fn get_user_mut(&mut self) -> &mut () {
+            &mut self.user
+        }
+    }
+
+    #[test]
+    fn find_byte_only_scans_the_packet_data_not_the_full_capacity() {
+        let mut buf = TestBuf::new(2048, 256);
+        assert_eq!(buf.find_byte(0), None);
+
+        let tail = buf.append(3);
+        tail.copy_from_slice(&[1, 2, 3]);
+        assert_eq!(buf.find_byte(2), Some(1));
+        assert_eq!(buf.find_byte(9), None);
+    }
+
+    #[test]
+    fn data_eq_only_compares_the_packet_data_not_the_full_capacity() {
+        let mut buf = TestBuf::new(2048, 256);
+
+        let tail = buf.append(3);
+        tail.copy_from_slice(&[1, 2, 3]);
+
+        assert!(buf.data_eq(&[1, 2, 3]));
+        assert!(!buf.data_eq(&[1, 2, 3, 4]));
+        assert!(!buf.data_eq(&[1, 2, 4]));
+    }
+
+    #[test]
+    fn prepend_then_adjust_head_round_trips() {
+        let mut buf = TestBuf::new(2048, 256);
+
+        let header = buf.prepend(14);
+        header.copy_from_slice(&[0xaa; 14]);
+        assert_eq!(buf.get_headroom(), 242);
+        assert_eq!(buf.get_len(), 14);
+
+        buf.adjust_head(14);
+        assert_eq!(buf.get_headroom(), 256);
+        assert_eq!(buf.get_len(), 0);
+    }
+
+    // adjust_head needs to work for headers that sit well past a quarter of the way into the buffer
+    #[test]
+    fn adjust_head_handles_a_large_header() {
+        let mut buf = TestBuf::new(2048, 256);
+        buf.set_len(1792);
+
+        buf.adjust_head(1500);
+
+        assert_eq!(buf.get_headroom(), 1756);
+        assert_eq!(buf.get_len(), 292);
+    }
+
+    #[test]
+    #[should_panic]
+    fn prepend_panics_when_larger_than_headroom() {
+        let mut buf = TestBuf::new(2048, 256);
+        buf.prepend(257);
+    }
+
+    #[test]
+    fn append_then_trim_round_trips() {
+        let mut buf = TestBuf::new(2048, 256);
+
+        let tail = buf.append(4);
+        tail.copy_from_slice(&[1, 2, 3, 4]);
+        assert_eq!(buf.get_len(), 4);
+
+        buf.trim(4);
+        assert_eq!(buf.get_len(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn append_panics_when_larger_than_capacity() {
+        let mut buf = TestBuf::new(2048, 256);
+        buf.append(1793);
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct TestHeader {
+        a: u16,
+        b: u16,
+    }
+
+    #[test]
+    fn read_write_header_round_trips() {
+        let mut buf = TestBuf::new(2048, 256);
+        buf.set_len(4);
+
+        let header = TestHeader {
+            a: 0x1122,
+            b: 0x3344,
+        };
+        buf.write_header(0, &header);
+
+        let read_back: TestHeader = buf.read_header(0).unwrap();
+        assert_eq!(read_back, header);
+    }
+
+    #[test]
+    fn read_header_out_of_bounds_returns_none() {
+        let mut buf = TestBuf::new(2048, 256);
+        buf.set_len(2);
+
+        let header: Option<TestHeader> = buf.read_header(0);
+        assert!(header.is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn write_header_panics_when_out_of_bounds() {
+        let mut buf = TestBuf::new(2048, 256);
+        buf.set_len(2);
+
+        let header = TestHeader { a: 1, b: 2 };
+        buf.write_header(0, &header);
+    }
+}
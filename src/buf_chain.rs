@@ -0,0 +1,168 @@
+use crate::buf::Buf;
+use crate::buf_mmap::BufMmap;
+
+/// BufChain links a sequence of [BufMmap](crate::buf_mmap::BufMmap) segments into a single logical
+/// packet, mirroring DPDK's mbuf chaining. This is needed because AF_XDP multi-buffer (frags) frames can
+/// span more than one umem chunk, which a single [BufMmap](crate::buf_mmap::BufMmap) cannot represent.
+#[derive(Debug)]
+pub struct BufChain<'a, T>
+where
+    T: std::default::Default,
+{
+    segments: Vec<BufMmap<'a, T>>,
+}
+
+impl<'a, T> BufChain<'a, T>
+where
+    T: std::default::Default,
+{
+    /// Creates a new chain starting with a single segment.
+    pub fn new(first: BufMmap<'a, T>) -> BufChain<'a, T> {
+        BufChain {
+            segments: vec![first],
+        }
+    }
+
+    /// Appends a segment to the tail of the chain, growing the logical packet so jumbo frames can be
+    /// built up from the buffer pool one umem chunk at a time.
+    pub fn push_segment(&mut self, segment: BufMmap<'a, T>) {
+        self.segments.push(segment);
+    }
+
+    /// Removes and returns the tail segment, shrinking the logical packet. Returns `None` if only one
+    /// segment remains, since a chain must always have at least one segment.
+    pub fn pop_segment(&mut self) -> Option<BufMmap<'a, T>> {
+        if self.segments.len() <= 1 {
+            return None;
+        }
+
+        self.segments.pop()
+    }
+
+    /// Returns the number of segments that make up this chain.
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Returns the total length, in bytes, of packet data across all segments.
+    pub fn total_len(&self) -> usize {
+        self.segments.iter().map(|s| s.get_len() as usize).sum()
+    }
+
+    /// Returns the segment containing the given logical offset into the chained packet, along with the
+    /// offset local to that segment, or `None` if `offset` is beyond the end of the chain.
+    pub fn segment_at(&self, offset: usize) -> Option<(&BufMmap<'a, T>, usize)> {
+        let mut remaining = offset;
+
+        for segment in &self.segments {
+            let len = segment.get_len() as usize;
+            if remaining < len {
+                return Some((segment, remaining));
+            }
+            remaining -= len;
+        }
+
+        None
+    }
+
+    /// Gathers the packet data of every segment, in order, into `dst`. Panics if `dst` is smaller than
+    /// [total_len](BufChain::total_len).
+    pub fn copy_out(&self, dst: &mut [u8]) {
+        let total = self.total_len();
+        if dst.len() < total {
+            panic!("dst too small {} vs total_len {}", dst.len(), total);
+        }
+
+        let mut offset = 0;
+        for segment in &self.segments {
+            let data = &segment.get_data()[..segment.get_len() as usize];
+            dst[offset..offset + data.len()].copy_from_slice(data);
+            offset += data.len();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BufChain;
+    use crate::buf::Buf;
+    use crate::buf_mmap::BufMmap;
+
+    #[derive(Default, Copy, Clone, Debug)]
+    struct BufCustom {}
+
+    fn make_segment(data: &mut [u8], len: u16) -> BufMmap<'_, BufCustom> {
+        BufMmap {
+            addr: 0,
+            len,
+            headroom: 0,
+            data,
+            user: Default::default(),
+        }
+    }
+
+    #[test]
+    fn tracks_segment_count_and_total_len() {
+        let mut seg0 = vec![1u8, 2, 3, 4];
+        let mut seg1 = vec![5u8, 6, 7];
+
+        let mut chain = BufChain::new(make_segment(&mut seg0, 4));
+        chain.push_segment(make_segment(&mut seg1, 3));
+
+        assert_eq!(chain.segment_count(), 2);
+        assert_eq!(chain.total_len(), 7);
+    }
+
+    #[test]
+    fn segment_at_finds_the_right_segment_and_local_offset() {
+        let mut seg0 = vec![1u8, 2, 3, 4];
+        let mut seg1 = vec![5u8, 6, 7];
+
+        let mut chain = BufChain::new(make_segment(&mut seg0, 4));
+        chain.push_segment(make_segment(&mut seg1, 3));
+
+        let (segment, local_offset) = chain.segment_at(5).unwrap();
+        assert_eq!(local_offset, 1);
+        assert_eq!(segment.get_data()[local_offset], 6);
+
+        assert!(chain.segment_at(7).is_none());
+    }
+
+    #[test]
+    fn copy_out_gathers_every_segment_in_order() {
+        let mut seg0 = vec![1u8, 2, 3, 4];
+        let mut seg1 = vec![5u8, 6, 7];
+
+        let mut chain = BufChain::new(make_segment(&mut seg0, 4));
+        chain.push_segment(make_segment(&mut seg1, 3));
+
+        let mut out = [0u8; 7];
+        chain.copy_out(&mut out);
+
+        assert_eq!(out, [1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn copy_out_panics_when_dst_is_too_small() {
+        let mut seg0 = vec![1u8, 2, 3, 4];
+        let chain = BufChain::new(make_segment(&mut seg0, 4));
+
+        let mut out = [0u8; 3];
+        chain.copy_out(&mut out);
+    }
+
+    #[test]
+    fn pop_segment_refuses_to_empty_the_chain() {
+        let mut seg0 = vec![1u8, 2, 3, 4];
+        let mut seg1 = vec![5u8, 6, 7];
+
+        let mut chain = BufChain::new(make_segment(&mut seg0, 4));
+        chain.push_segment(make_segment(&mut seg1, 3));
+
+        assert!(chain.pop_segment().is_some());
+        assert_eq!(chain.segment_count(), 1);
+        assert!(chain.pop_segment().is_none());
+        assert_eq!(chain.segment_count(), 1);
+    }
+}
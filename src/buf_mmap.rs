@@ -57,8 +57,8 @@ where
     }
 
     fn set_headroom(&mut self, headroom: usize) {
-        if headroom > self.get_capacity() as usize - self.headroom {
-            panic!("headroom too large headroom {} vs {}", headroom, self.get_capacity() as usize + self.headroom);
+        if headroom > self.data.len() {
+            panic!("headroom too large headroom {} vs buffer size {}", headroom, self.data.len());
         }
         self.headroom = headroom;
     }
@@ -0,0 +1,183 @@
+use std::marker::PhantomData;
+
+use crate::buf::Buf;
+
+/// BufReader is a sequential, position-tracking cursor over a [Buf](crate::buf::Buf), modeled on the
+/// `bytes` crate's `Buf` trait. It replaces manual slice indexing into
+/// [get_data](crate::buf::Buf::get_data) with endianness-aware, bounds-checked reads, removing a whole
+/// class of off-by-one errors from header parsing.
+pub struct BufReader<'a, B, T>
+where
+    B: Buf<T>,
+    T: std::default::Default,
+{
+    buf: &'a B,
+    pos: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, B, T> BufReader<'a, B, T>
+where
+    B: Buf<T>,
+    T: std::default::Default,
+{
+    /// Creates a new reader positioned at the start of `buf`'s packet data.
+    pub fn new(buf: &'a B) -> BufReader<'a, B, T> {
+        BufReader {
+            buf,
+            pos: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the number of bytes remaining between the current position and the end of the packet data.
+    pub fn remaining(&self) -> usize {
+        self.buf.get_len() as usize - self.pos
+    }
+
+    /// Advances the position by `len` bytes without reading them. Panics if `len` is greater than
+    /// [remaining](BufReader::remaining).
+    pub fn advance(&mut self, len: usize) {
+        if len > self.remaining() {
+            panic!("advance len too large {} vs remaining {}", len, self.remaining());
+        }
+        self.pos += len;
+    }
+
+    fn take(&mut self, len: usize) -> &[u8] {
+        if len > self.remaining() {
+            panic!("read past end of buffer, {} requested, {} remaining", len, self.remaining());
+        }
+
+        let data = &self.buf.get_data()[self.pos..self.pos + len];
+        self.pos += len;
+        data
+    }
+
+    /// Reads a single byte, advancing the position by one.
+    pub fn get_u8(&mut self) -> u8 {
+        self.take(1)[0]
+    }
+
+    /// Reads a big-endian `u16`, advancing the position by two.
+    pub fn get_u16(&mut self) -> u16 {
+        let b = self.take(2);
+        u16::from_be_bytes([b[0], b[1]])
+    }
+
+    /// Reads a little-endian `u16`, advancing the position by two.
+    pub fn get_u16_le(&mut self) -> u16 {
+        let b = self.take(2);
+        u16::from_le_bytes([b[0], b[1]])
+    }
+
+    /// Reads a big-endian `u32`, advancing the position by four.
+    pub fn get_u32(&mut self) -> u32 {
+        let b = self.take(4);
+        u32::from_be_bytes([b[0], b[1], b[2], b[3]])
+    }
+
+    /// Reads a little-endian `u32`, advancing the position by four.
+    pub fn get_u32_le(&mut self) -> u32 {
+        let b = self.take(4);
+        u32::from_le_bytes([b[0], b[1], b[2], b[3]])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BufReader;
+    use crate::buf::Buf;
+
+    /// A minimal [Buf](crate::buf::Buf) impl with no headroom, used only to exercise BufReader.
+    struct TestBuf {
+        data: Vec<u8>,
+        len: u16,
+        user: (),
+    }
+
+    impl TestBuf {
+        fn from_bytes(bytes: &[u8]) -> TestBuf {
+            TestBuf {
+                data: bytes.to_vec(),
+                len: bytes.len() as u16,
+                user: (),
+            }
+        }
+    }
+
+    impl Buf<()> for TestBuf {
+        fn get_data(&self) -> &[u8] {
+            &self.data
+        }
+
+        fn get_data_mut(&mut self) -> &mut [u8] {
+            &mut self.data
+        }
+
+        fn get_data_with_headroom(&self) -> &[u8] {
+            &self.data
+        }
+
+        fn get_data_with_headroom_mut(&mut self) -> &mut [u8] {
+            &mut self.data
+        }
+
+        fn get_capacity(&self) -> u16 {
+            self.data.len() as u16
+        }
+
+        fn get_len(&self) -> u16 {
+            self.len
+        }
+
+        fn set_headroom(&mut self, _headroom: usize) {}
+
+        fn get_headroom(&self) -> usize {
+            0
+        }
+
+        fn set_len(&mut self, len: u16) {
+            self.len = len;
+        }
+
+        fn get_user(&self) -> &() {
+            &self.user
+        }
+
+        fn get_user_mut(&mut self) -> &mut () {
+            &mut self.user
+        }
+    }
+
+    #[test]
+    fn reads_values_sequentially_and_tracks_remaining() {
+        let buf = TestBuf::from_bytes(&[0xAB, 0x01, 0x02, 0x03, 0x04, 0x10, 0x20, 0x30, 0x40]);
+        let mut reader = BufReader::<TestBuf, ()>::new(&buf);
+
+        assert_eq!(reader.remaining(), 9);
+        assert_eq!(reader.get_u8(), 0xAB);
+        assert_eq!(reader.get_u16(), 0x0102);
+        assert_eq!(reader.get_u16_le(), 0x0403);
+        assert_eq!(reader.get_u32(), 0x10203040);
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn advance_skips_bytes_without_reading_them() {
+        let buf = TestBuf::from_bytes(&[0x01, 0x02, 0x03, 0x04]);
+        let mut reader = BufReader::<TestBuf, ()>::new(&buf);
+
+        reader.advance(2);
+        assert_eq!(reader.get_u16(), 0x0304);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_u8_panics_when_past_the_end() {
+        let buf = TestBuf::from_bytes(&[]);
+        let mut reader = BufReader::<TestBuf, ()>::new(&buf);
+
+        reader.get_u8();
+    }
+}
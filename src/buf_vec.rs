@@ -0,0 +1,216 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::buf::Buf;
+use crate::AF_XDP_RESERVED;
+
+/// BufVec is the [Buf](crate::buf::Buf) implementation backed by a heap-allocated `Vec<u8>` instead of
+/// AF_XDP mapped memory. It exists so that packet pipelines can be built, unit tested, and fuzzed on
+/// machines without AF_XDP support or `CAP_NET_RAW`, as described on [Buf](crate::buf::Buf).
+#[derive(Debug)]
+pub struct BufVec<T>
+where
+    T: std::default::Default,
+{
+    /// len is the length of the buffer that is valid packet data
+    pub(crate) len: u16,
+    /// headroom is the number of bytes in the buffer prior to the packet data
+    pub(crate) headroom: usize,
+    /// data is the Vec<u8> that contains the packet data
+    pub(crate) data: Vec<u8>,
+    /// user is the user defined type
+    pub(crate) user: T,
+}
+
+impl<T> Buf<T> for BufVec<T>
+where
+    T: std::default::Default,
+{
+    fn get_data(&self) -> &[u8] {
+        &self.data[self.headroom..]
+    }
+
+    fn get_data_mut(&mut self) -> &mut [u8] {
+        &mut self.data[self.headroom..]
+    }
+
+    fn get_data_with_headroom(&self) -> &[u8] {
+        &self.data[0..]
+    }
+
+    fn get_data_with_headroom_mut(&mut self) -> &mut [u8] {
+        &mut self.data[0..]
+    }
+
+    fn get_capacity(&self) -> u16 {
+        u16::try_from(self.data.len() - self.headroom).unwrap()
+    }
+
+    fn get_len(&self) -> u16 {
+        self.len
+    }
+
+    fn set_len(&mut self, len: u16) {
+        if len > self.get_capacity() {
+            panic!("len too large {} vs {}", len, self.get_capacity());
+        }
+        self.len = len;
+    }
+
+    fn set_headroom(&mut self, headroom: usize) {
+        if headroom > self.data.len() {
+            panic!("headroom too large headroom {} vs buffer size {}", headroom, self.data.len());
+        }
+        self.headroom = headroom;
+    }
+
+    fn get_headroom(&self) -> usize {
+        self.headroom
+    }
+
+    fn get_user(&self) -> &T {
+        &self.user
+    }
+
+    fn get_user_mut(&mut self) -> &mut T {
+        &mut self.user
+    }
+}
+
+impl<T> fmt::Display for BufVec<T>
+where
+    T: std::default::Default,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "BufVec len={} capacity={} headroom={} data={:?}",
+            self.len,
+            self.get_capacity(),
+            self.headroom,
+            self.data.as_ptr()
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum VecError {
+    Failed,
+}
+
+/// VecArea is a heap-allocated stand-in for [MmapArea](crate::mmap_area::MmapArea) that hands out
+/// [BufVec](crate::buf_vec::BufVec) buffers instead of buffers backed by `mmap`. No `mmap`/`libc` calls
+/// are made, so pipeline code built against [Buf](crate::buf::Buf) can be exercised without AF_XDP
+/// infrastructure.
+#[derive(Debug)]
+pub struct VecArea {
+    buf_num: usize,
+    buf_len: usize,
+}
+
+impl VecArea {
+    /// Allocate a new Vec-backed area based on the size and number of buffers
+    ///
+    /// # Arguments
+    ///
+    /// * buf_num: The number of buffers to allocate
+    /// * buf_len: The length of each buffer
+    pub fn new<T: std::default::Default>(
+        buf_num: usize,
+        buf_len: usize,
+    ) -> Result<(VecArea, Vec<BufVec<T>>), VecError> {
+        let va = VecArea { buf_num, buf_len };
+
+        let mut bufs = Vec::with_capacity(buf_num);
+
+        for _ in 0..buf_num {
+            bufs.push(BufVec::<T> {
+                len: 0,
+                headroom: AF_XDP_RESERVED.into(),
+                data: vec![0u8; buf_len],
+                user: Default::default(),
+            });
+        }
+
+        Ok((va, bufs))
+    }
+
+    /// Get the number of buffers in the area.
+    pub fn get_buf_num(&self) -> usize {
+        self.buf_num
+    }
+
+    /// Get the size of the buffers in the area.
+    pub(crate) fn get_buf_len(&self) -> usize {
+        self.buf_len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VecArea, VecError};
+    use crate::buf::Buf;
+    use crate::buf_vec::BufVec;
+    use crate::AF_XDP_RESERVED;
+
+    #[derive(Default, Copy, Clone, Debug)]
+    struct BufCustom {}
+
+    /// Test that bufs ends up with the correct number of buffers and each is the correct length
+    #[test]
+    fn bufs_to_pool() {
+        const BUF_NUM: usize = 1024;
+        const BUF_LEN: usize = 2048;
+
+        let r: Result<(VecArea, Vec<BufVec<BufCustom>>), VecError> = VecArea::new(BUF_NUM, BUF_LEN);
+
+        let (area, bufs) = match r {
+            Ok((area, bufs)) => (area, bufs),
+            Err(err) => panic!("{:?}", err),
+        };
+
+        assert_eq!(area.buf_num, BUF_NUM);
+        assert_eq!(area.buf_len, BUF_LEN);
+        assert_eq!(bufs.len(), BUF_NUM);
+
+        for buf in bufs {
+            if buf.get_data().len() != BUF_LEN - AF_XDP_RESERVED as usize {
+                panic!(
+                    "expected buf len {} found {}",
+                    BUF_LEN,
+                    buf.get_data().len()
+                );
+            }
+        }
+    }
+
+    // Test that set_headroom/prepend/adjust_head work on a BufVec the same way they do on a BufMmap, even
+    // when the header strips well past a quarter of the way into the buffer
+    #[test]
+    fn prepend_and_adjust_head() {
+        const BUF_LEN: usize = 2048;
+
+        let r: Result<(VecArea, Vec<BufVec<BufCustom>>), VecError> = VecArea::new(1, BUF_LEN);
+        let (_area, mut bufs) = match r {
+            Ok((area, bufs)) => (area, bufs),
+            Err(err) => panic!("{:?}", err),
+        };
+
+        let buf = &mut bufs[0];
+
+        let starting_headroom = buf.get_headroom();
+
+        let header = buf.prepend(14);
+        header.copy_from_slice(&[0xaa; 14]);
+        assert_eq!(buf.get_headroom(), starting_headroom - 14);
+        assert_eq!(buf.get_len(), 14);
+
+        buf.adjust_head(14);
+        assert_eq!(buf.get_headroom(), starting_headroom);
+        assert_eq!(buf.get_len(), 0);
+
+        buf.set_len(BUF_LEN as u16 - starting_headroom as u16);
+        buf.adjust_head(1500);
+        assert_eq!(buf.get_headroom(), starting_headroom + 1500);
+    }
+}
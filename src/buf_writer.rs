@@ -0,0 +1,150 @@
+use std::marker::PhantomData;
+
+use crate::buf::Buf;
+
+/// BufWriter is a sequential cursor that serializes values onto the tail of a [Buf](crate::buf::Buf),
+/// modeled on the `bytes` crate's `BufMut` trait. Each `put_*` call extends the buffer's length via
+/// [append](crate::buf::Buf::append), so callers can build up a packet field by field without tracking
+/// offsets by hand.
+pub struct BufWriter<'a, B, T>
+where
+    B: Buf<T>,
+    T: std::default::Default,
+{
+    buf: &'a mut B,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, B, T> BufWriter<'a, B, T>
+where
+    B: Buf<T>,
+    T: std::default::Default,
+{
+    /// Creates a new writer that appends to the tail of `buf`.
+    pub fn new(buf: &'a mut B) -> BufWriter<'a, B, T> {
+        BufWriter {
+            buf,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Appends a single byte.
+    pub fn put_u8(&mut self, val: u8) {
+        self.buf.append(1)[0] = val;
+    }
+
+    /// Appends a big-endian `u16`.
+    pub fn put_u16(&mut self, val: u16) {
+        self.buf.append(2).copy_from_slice(&val.to_be_bytes());
+    }
+
+    /// Appends a little-endian `u16`.
+    pub fn put_u16_le(&mut self, val: u16) {
+        self.buf.append(2).copy_from_slice(&val.to_le_bytes());
+    }
+
+    /// Appends a big-endian `u32`.
+    pub fn put_u32(&mut self, val: u32) {
+        self.buf.append(4).copy_from_slice(&val.to_be_bytes());
+    }
+
+    /// Appends a little-endian `u32`.
+    pub fn put_u32_le(&mut self, val: u32) {
+        self.buf.append(4).copy_from_slice(&val.to_le_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BufWriter;
+    use crate::buf::Buf;
+
+    /// A minimal [Buf](crate::buf::Buf) impl with no headroom, used only to exercise BufWriter.
+    struct TestBuf {
+        data: Vec<u8>,
+        len: u16,
+        user: (),
+    }
+
+    impl TestBuf {
+        fn with_capacity(capacity: usize) -> TestBuf {
+            TestBuf {
+                data: vec![0u8; capacity],
+                len: 0,
+                user: (),
+            }
+        }
+    }
+
+    impl Buf<()> for TestBuf {
+        fn get_data(&self) -> &[u8] {
+            &self.data
+        }
+
+        fn get_data_mut(&mut self) -> &mut [u8] {
+            &mut self.data
+        }
+
+        fn get_data_with_headroom(&self) -> &[u8] {
+            &self.data
+        }
+
+        fn get_data_with_headroom_mut(&mut self) -> &mut [u8] {
+            &mut self.data
+        }
+
+        fn get_capacity(&self) -> u16 {
+            self.data.len() as u16
+        }
+
+        fn get_len(&self) -> u16 {
+            self.len
+        }
+
+        fn set_headroom(&mut self, _headroom: usize) {}
+
+        fn get_headroom(&self) -> usize {
+            0
+        }
+
+        fn set_len(&mut self, len: u16) {
+            self.len = len;
+        }
+
+        fn get_user(&self) -> &() {
+            &self.user
+        }
+
+        fn get_user_mut(&mut self) -> &mut () {
+            &mut self.user
+        }
+    }
+
+    #[test]
+    fn writes_values_sequentially_and_extends_len() {
+        let mut buf = TestBuf::with_capacity(16);
+
+        {
+            let mut writer = BufWriter::<TestBuf, ()>::new(&mut buf);
+            writer.put_u8(0xAB);
+            writer.put_u16(0x0102);
+            writer.put_u16_le(0x0304);
+            writer.put_u32(0x10203040);
+        }
+
+        assert_eq!(buf.get_len(), 9);
+        assert_eq!(
+            &buf.get_data()[..9],
+            &[0xAB, 0x01, 0x02, 0x04, 0x03, 0x10, 0x20, 0x30, 0x40]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn put_panics_when_it_does_not_fit() {
+        let mut buf = TestBuf::with_capacity(1);
+        let mut writer = BufWriter::<TestBuf, ()>::new(&mut buf);
+
+        writer.put_u16(0x0102);
+    }
+}
@@ -0,0 +1,13 @@
+pub mod buf;
+pub mod buf_chain;
+pub mod buf_mmap;
+pub mod buf_reader;
+pub mod buf_writer;
+pub mod mmap_area;
+pub mod size_of;
+
+#[cfg(feature = "vec_memory")]
+pub mod buf_vec;
+
+/// The number of bytes reserved at the front of each buffer for AF_XDP/XDP metadata.
+pub(crate) const AF_XDP_RESERVED: u16 = 256;
@@ -1,10 +1,12 @@
+use std::ffi::CString;
+use std::os::unix::io::RawFd;
 use std::sync::Arc;
 use std::{marker::PhantomData, u64};
 
 use errno::errno;
 use libc::{
-    c_int, c_void, mmap, munmap, MAP_ANONYMOUS, MAP_FAILED, MAP_HUGETLB, MAP_PRIVATE, PROT_READ,
-    PROT_WRITE,
+    c_int, c_void, close, ftruncate, mmap, munmap, MAP_ANONYMOUS, MAP_FAILED, MAP_HUGETLB,
+    MAP_PRIVATE, MAP_SHARED, PROT_READ, PROT_WRITE,
 };
 
 use crate::buf_mmap::BufMmap;
@@ -34,10 +36,33 @@ pub enum MmapError {
 }
 
 /// Configuration options for MmapArea
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct MmapAreaOptions {
     /// If set to true, the mmap call is passed MAP_HUGETLB
     pub huge_tlb: bool,
+
+    /// The alignment, in bytes, each buffer's packet data is guaranteed to start on. Both `buf_len` and
+    /// the headroom preceding the packet data are rounded up to a multiple of this, so that vectorized
+    /// parsing (e.g. the SIMD helpers on [Buf](crate::buf::Buf)) can operate over aligned lanes, including
+    /// for values that do not divide the reserved headroom. Buffers are placed relative to the start of the
+    /// mapping, which is only page aligned, so the guarantee holds only for values that divide the page
+    /// size. Defaults to 64.
+    pub align: usize,
+}
+
+impl Default for MmapAreaOptions {
+    fn default() -> Self {
+        MmapAreaOptions {
+            huge_tlb: false,
+            align: 64,
+        }
+    }
+}
+
+/// Rounds `buf_len` up to the next multiple of `align` (treating an `align` of 0 as 1).
+fn align_up(buf_len: usize, align: usize) -> usize {
+    let align = if align == 0 { 1 } else { align };
+    buf_len.div_ceil(align) * align
 }
 
 impl<'a, T: std::default::Default + std::marker::Copy> MmapArea<'a, T> {
@@ -53,6 +78,8 @@ impl<'a, T: std::default::Default + std::marker::Copy> MmapArea<'a, T> {
         buf_len: usize,
         options: MmapAreaOptions,
     ) -> Result<(Arc<MmapArea<'a, T>>, Vec<BufMmap<'a, T>>), MmapError> {
+        let buf_len = align_up(buf_len, options.align);
+
         let ptr: *mut c_void;
         let mut flags: c_int = MAP_PRIVATE | MAP_ANONYMOUS;
 
@@ -82,9 +109,122 @@ impl<'a, T: std::default::Default + std::marker::Copy> MmapArea<'a, T> {
             phantom: PhantomData,
         });
 
-        // Create the bufs
+        let bufs = Self::build_bufs(&ma, buf_num, buf_len, options.align);
+
+        Ok((ma, bufs))
+    }
+
+    /// Allocate a new memory mapped area backed by an anonymous file created with `memfd_create`, mapped
+    /// `MAP_SHARED` so the umem can be shared with another process (for example a separate control/stats
+    /// process or a sidecar).
+    ///
+    /// Returns the raw file descriptor for the memfd alongside the area so it can be passed to another
+    /// process over a unix socket with `SCM_RIGHTS`, which can then re-map the same memory with
+    /// [from_fd](MmapArea::from_fd). The mapping stays valid independently of the fd, so the returned fd
+    /// is owned by the caller: [MmapArea] does not hold or close it, and the caller is responsible for
+    /// closing it once it has been sent to (or is no longer needed by) any peer.
+    ///
+    /// # Arguments
+    ///
+    /// * name: A name for the memfd, visible in `/proc/self/fd/*` for debugging
+    /// * buf_num: The number of buffers to allocate in the memory mapped area
+    /// * buf_len: The length of each buffer
+    /// * options: Configuration options
+    pub fn new_memfd(
+        name: &str,
+        buf_num: usize,
+        buf_len: usize,
+        options: MmapAreaOptions,
+    ) -> Result<(Arc<MmapArea<'a, T>>, Vec<BufMmap<'a, T>>, RawFd), MmapError> {
+        let buf_len = align_up(buf_len, options.align);
+
+        let c_name = CString::new(name).map_err(|_| MmapError::Failed)?;
+
+        let fd = unsafe { libc::memfd_create(c_name.as_ptr(), 0) };
+        if fd < 0 {
+            return Err(MmapError::Failed);
+        }
+
+        let size = (buf_num * buf_len) as libc::off_t;
+        if unsafe { ftruncate(fd, size) } != 0 {
+            unsafe { close(fd) };
+            return Err(MmapError::Failed);
+        }
+
+        let (ma, bufs) = match Self::from_fd(fd, buf_num, buf_len, options) {
+            Ok(ok) => ok,
+            Err(err) => {
+                unsafe { close(fd) };
+                return Err(err);
+            }
+        };
+
+        Ok((ma, bufs, fd))
+    }
+
+    /// Maps a shareable fd sized to `buf_num * buf_len`, typically a memfd received from another process
+    /// over a unix socket via `SCM_RIGHTS`, or one previously created with
+    /// [new_memfd](MmapArea::new_memfd). Does not take ownership of `fd`: the mapping remains valid after
+    /// `fd` is closed, so the caller keeps whatever responsibility it already had for closing it.
+    ///
+    /// # Arguments
+    ///
+    /// * fd: A file descriptor referring to memory already sized to `buf_num * buf_len`
+    /// * buf_num: The number of buffers in the memory mapped area
+    /// * buf_len: The length of each buffer, already rounded up to alignment if applicable
+    /// * options: Configuration options
+    pub fn from_fd(
+        fd: RawFd,
+        buf_num: usize,
+        buf_len: usize,
+        options: MmapAreaOptions,
+    ) -> Result<(Arc<MmapArea<'a, T>>, Vec<BufMmap<'a, T>>), MmapError> {
+        let ptr: *mut c_void;
+        let mut flags: c_int = MAP_SHARED;
+
+        if options.huge_tlb {
+            flags |= MAP_HUGETLB
+        }
+
+        unsafe {
+            ptr = mmap(
+                std::ptr::null_mut::<c_void>(),
+                buf_num * buf_len,
+                PROT_READ | PROT_WRITE,
+                flags,
+                fd,
+                0,
+            );
+        }
+
+        if ptr == MAP_FAILED {
+            return Err(MmapError::Failed);
+        }
+
+        let ma = Arc::new(MmapArea {
+            buf_num,
+            buf_len,
+            ptr,
+            phantom: PhantomData,
+        });
+
+        let bufs = Self::build_bufs(&ma, buf_num, buf_len, options.align);
+
+        Ok((ma, bufs))
+    }
+
+    /// Carves `buf_num` [BufMmap](crate::buf_mmap::BufMmap)s of `buf_len` bytes each out of `ma`'s mapped
+    /// area. The headroom of each buffer is rounded up to `align` as well as `buf_len`, so that the
+    /// packet-data pointer (which starts after the headroom) lands on the same alignment guaranteed for
+    /// the buffer itself.
+    fn build_bufs(
+        ma: &Arc<MmapArea<'a, T>>,
+        buf_num: usize,
+        buf_len: usize,
+        align: usize,
+    ) -> Vec<BufMmap<'a, T>> {
+        let headroom = align_up(AF_XDP_RESERVED as usize, align);
         let mut bufs = Vec::with_capacity(buf_num);
-        let buf_len_available = buf_len as usize;
 
         for i in 0..buf_num {
             let buf: BufMmap<T>;
@@ -96,16 +236,16 @@ impl<'a, T: std::default::Default + std::marker::Copy> MmapArea<'a, T> {
                 buf = BufMmap::<T> {
                     addr,
                     len: 0,
-                    data: std::slice::from_raw_parts_mut(ptr as *mut u8, buf_len_available),
+                    data: std::slice::from_raw_parts_mut(ptr as *mut u8, buf_len),
                     user: Default::default(),
-                    headroom: AF_XDP_RESERVED.try_into().unwrap()
+                    headroom,
                 };
             }
 
             bufs.push(buf);
         }
 
-        Ok((ma, bufs))
+        bufs
     }
 
     /// Return the ptr to the memory mapped area.
@@ -146,6 +286,7 @@ impl<'a, T: std::default::Default + std::marker::Copy> Drop for MmapArea<'a, T>
 #[cfg(test)]
 mod tests {
     use std::convert::TryInto;
+    use std::os::unix::io::RawFd;
     use std::sync::Arc;
 
     use super::{MmapArea, MmapAreaOptions, MmapError};
@@ -162,7 +303,7 @@ mod tests {
         const BUF_NUM: usize = 1024;
         const BUF_LEN: usize = 2048;
 
-        let options = MmapAreaOptions { huge_tlb: false };
+        let options = MmapAreaOptions::default();
         let r: Result<(Arc<MmapArea<BufCustom>>, Vec<BufMmap<BufCustom>>), MmapError> =
             MmapArea::new(BUF_NUM, BUF_LEN, options);
 
@@ -192,7 +333,7 @@ mod tests {
         const BUF_NUM: usize = 1024;
         const BUF_LEN: usize = 2048;
 
-        let options = MmapAreaOptions { huge_tlb: false };
+        let options = MmapAreaOptions::default();
         let r: Result<(Arc<MmapArea<BufCustom>>, Vec<BufMmap<BufCustom>>), MmapError> =
             MmapArea::new(BUF_NUM, BUF_LEN, options);
 
@@ -239,7 +380,7 @@ mod tests {
         const BUF_NUM: usize = 256;
         const BUF_LEN: usize = 2048;
 
-        let options = MmapAreaOptions { huge_tlb: false };
+        let options = MmapAreaOptions::default();
         let r: Result<(Arc<MmapArea<BufCustom>>, Vec<BufMmap<BufCustom>>), MmapError> =
             MmapArea::new(BUF_NUM, BUF_LEN, options);
 
@@ -270,4 +411,78 @@ mod tests {
             }
         }
     }
+
+    // Test that buf_len is rounded up to the requested alignment and that every buffer's packet data
+    // pointer lands on that alignment, including for an alignment that does not evenly divide
+    // AF_XDP_RESERVED (i.e. the headroom has to be rounded up too, not just buf_len)
+    #[test]
+    fn buf_align() {
+        const BUF_NUM: usize = 64;
+        const BUF_LEN: usize = 2003;
+        const ALIGN: usize = 512;
+
+        let options = MmapAreaOptions {
+            huge_tlb: false,
+            align: ALIGN,
+        };
+        let r: Result<(Arc<MmapArea<BufCustom>>, Vec<BufMmap<BufCustom>>), MmapError> =
+            MmapArea::new(BUF_NUM, BUF_LEN, options);
+
+        let (area, bufs) = match r {
+            Ok((area, bufs)) => (area, bufs),
+            Err(err) => panic!("{:?}", err),
+        };
+
+        assert_eq!(area.buf_len % ALIGN, 0);
+        assert!(area.buf_len >= BUF_LEN);
+
+        for buf in &bufs {
+            let ptr = buf.get_data().as_ptr() as usize;
+            assert_eq!(ptr % ALIGN, 0);
+        }
+    }
+
+    // Test that a memfd-backed area can be re-mapped from its raw fd, and that writes through one
+    // mapping are visible through the other since they share the same underlying memory
+    #[test]
+    fn memfd_shared_with_peer() {
+        const BUF_NUM: usize = 8;
+        const BUF_LEN: usize = 2048;
+
+        let options = MmapAreaOptions::default();
+        let r: Result<(Arc<MmapArea<BufCustom>>, Vec<BufMmap<BufCustom>>, RawFd), MmapError> =
+            MmapArea::new_memfd("afxdp-rs-test", BUF_NUM, BUF_LEN, options);
+
+        let (area, mut bufs, fd) = match r {
+            Ok((area, bufs, fd)) => (area, bufs, fd),
+            Err(err) => panic!("{:?}", err),
+        };
+
+        assert_eq!(area.buf_num, BUF_NUM);
+        assert_eq!(bufs.len(), BUF_NUM);
+
+        bufs[0].data[0] = 0xab;
+
+        let peer_options = MmapAreaOptions::default();
+        let r: Result<(Arc<MmapArea<BufCustom>>, Vec<BufMmap<BufCustom>>), MmapError> =
+            MmapArea::from_fd(fd, BUF_NUM, area.buf_len, peer_options);
+
+        let (_peer_area, peer_bufs) = match r {
+            Ok((area, bufs)) => (area, bufs),
+            Err(err) => panic!("{:?}", err),
+        };
+
+        assert_eq!(peer_bufs[0].data[0], 0xab);
+
+        // Neither MmapArea took ownership of fd, so dropping both must not close it out from under the
+        // caller, who is the only one responsible for that.
+        drop(area);
+        drop(_peer_area);
+
+        assert_ne!(unsafe { libc::fcntl(fd, libc::F_GETFD) }, -1);
+
+        unsafe {
+            libc::close(fd);
+        }
+    }
 }
@@ -0,0 +1,13 @@
+/// SizeOf reports the byte size of a type when it is mapped directly onto buffer memory, mirroring
+/// capsule's packet header model. A blanket implementation is provided for every `Sized` type, defaulting
+/// to `mem::size_of::<Self>()`, so header structs (e.g. Ethernet, IPv4) need no custom implementation.
+pub trait SizeOf {
+    /// Returns the size, in bytes, of this type when read from or written to a buffer.
+    fn size_of() -> usize;
+}
+
+impl<T> SizeOf for T {
+    fn size_of() -> usize {
+        std::mem::size_of::<T>()
+    }
+}